@@ -0,0 +1,65 @@
+use futures::channel::oneshot::Sender as OneshotSender;
+use futures::channel::{mpsc, oneshot};
+use serde_json::Value;
+
+use chromiumoxid_types::{MethodId, Request as CdpRequest, Response};
+
+use crate::error::CdpError;
+use crate::handler::{HandlerMessage, TargetFilter};
+use crate::page::Page;
+
+/// A single command submitted through the public API, forwarded to the
+/// `Handler` for dispatch.
+pub(crate) struct CommandMessage {
+    pub method: MethodId,
+    pub session_id: Option<chromiumoxid_types::SessionId>,
+    pub params: Value,
+    pub sender: OneshotSender<Result<Response, CdpError>>,
+}
+
+impl CommandMessage {
+    /// Whether this command is a navigation (`Page.navigate`), which the
+    /// handler routes through a target's navigation lifecycle instead of
+    /// dispatching it as a plain external command.
+    pub(crate) fn is_navigation(&self) -> bool {
+        self.method.as_ref() == "Page.navigate"
+    }
+
+    /// Splits this message into the raw CDP request and the sender that
+    /// should eventually receive its response.
+    pub(crate) fn split(self) -> (CdpRequest, OneshotSender<Result<Response, CdpError>>) {
+        (
+            CdpRequest {
+                method: self.method,
+                session_id: self.session_id,
+                params: self.params,
+            },
+            self.sender,
+        )
+    }
+}
+
+/// A handle to a running browser instance, owning the channel used to talk
+/// to the `Handler` task running in the background.
+#[derive(Clone)]
+pub struct Browser {
+    sender: mpsc::Sender<HandlerMessage>,
+}
+
+impl Browser {
+    pub(crate) fn new(sender: mpsc::Sender<HandlerMessage>) -> Self {
+        Self { sender }
+    }
+
+    /// Resolves with the first target matching `filter`, be it one that
+    /// already exists or the next one to be created (e.g. a popup opened via
+    /// `window.open`).
+    pub async fn wait_for_target(&self, filter: TargetFilter) -> Result<Page, CdpError> {
+        let (tx, rx) = oneshot::channel();
+        let mut sender = self.sender.clone();
+        sender
+            .try_send(HandlerMessage::WaitForTarget { filter, tx })
+            .map_err(|_| CdpError::Disconnected)?;
+        rx.await.map_err(|_| CdpError::Disconnected)
+    }
+}
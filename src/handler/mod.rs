@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use fnv::FnvHashMap;
-use futures::channel::mpsc::Receiver;
+use futures::channel::mpsc::{self, Receiver};
 use futures::channel::oneshot::Sender as OneshotSender;
 use futures::stream::{Fuse, Stream, StreamExt};
 use futures::task::{Context, Poll};
@@ -31,6 +31,87 @@ use crate::{
 /// Standard timeout in MS
 pub const REQUEST_TIMEOUT: u64 = 30000;
 
+/// Configures how long the [`Handler`] waits for a response to a submitted
+/// command before evicting it and failing the waiting caller.
+#[derive(Debug, Clone)]
+pub struct HandlerConfig {
+    /// The timeout applied to a command unless it has a method-specific
+    /// override in `request_timeouts`.
+    pub request_timeout: Duration,
+    /// Per-method timeout overrides, keyed by the CDP method identifier
+    /// (e.g. `"Page.navigate"`), for commands known to be slow or flaky.
+    pub request_timeouts: HashMap<&'static str, Duration>,
+    /// Governs whether and how the handler reconnects after the websocket
+    /// connection to the browser drops.
+    pub reconnect: ReconnectPolicy,
+}
+
+impl HandlerConfig {
+    fn timeout_for(&self, method: &str) -> Duration {
+        self.request_timeouts
+            .get(method)
+            .copied()
+            .unwrap_or(self.request_timeout)
+    }
+}
+
+impl Default for HandlerConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_millis(REQUEST_TIMEOUT),
+            request_timeouts: Default::default(),
+            reconnect: Default::default(),
+        }
+    }
+}
+
+/// Controls the exponential backoff the handler uses when the websocket
+/// connection to the browser is lost and needs to be re-dialed.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Whether the handler should attempt to reconnect at all; when `false`
+    /// a transport error is surfaced immediately, ending the handler stream.
+    pub enabled: bool,
+    /// Delay before the first reconnect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound the exponential backoff is capped at.
+    pub max_backoff: Duration,
+    /// Maximum number of reconnect attempts before giving up, or `None` for
+    /// unlimited retries.
+    pub max_retries: Option<usize>,
+}
+
+impl ReconnectPolicy {
+    fn backoff_for(&self, attempt: usize) -> Duration {
+        let mut backoff = self.initial_backoff;
+        for _ in 0..attempt.min(16) {
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+        backoff.min(self.max_backoff)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_retries: None,
+        }
+    }
+}
+
+/// Tracks an in-progress reconnection attempt.
+#[derive(Debug)]
+struct ReconnectState {
+    attempt: usize,
+    next_attempt: Instant,
+    /// Whether a wake-up timer for `next_attempt` has already been armed, so
+    /// we don't spawn one on every poll while waiting out the backoff.
+    timer_armed: bool,
+}
+
 mod browser;
 mod cmd;
 pub mod emulation;
@@ -61,15 +142,36 @@ pub struct Handler {
     ///
     /// There can be multiple sessions per target.
     sessions: HashMap<SessionId, Session>,
+    /// Subscribers that asked to be notified of every event for a given CDP
+    /// method, keyed by the method's identifier (e.g. `"Network.requestWillBeSent"`).
+    subscriptions: HashMap<&'static str, Vec<EventListener>>,
+    /// Raw CDP passthrough sessions attached via
+    /// `HandlerMessage::AttachRawSession`, keyed by a locally assigned id.
+    raw_sessions: HashMap<ProxyId, RawSessionProxy>,
     /// The websocket connection to the chromium instance
     conn: Connection<CdpEventMessage>,
     evict_command_timeout: PeriodicJob,
     /// The internal identifier for a specific navigation
     next_navigation_id: usize,
+    /// The internal identifier for the next raw session proxy
+    next_proxy_id: usize,
+    /// Per-command timeout configuration
+    config: HandlerConfig,
+    /// Set while the handler is backing off and retrying a dropped
+    /// connection; `None` means the connection is healthy.
+    reconnect: Option<ReconnectState>,
+    /// Waiters registered via `HandlerMessage::WaitForTarget`, resolved the
+    /// moment a target matching their filter shows up with an initialized
+    /// page.
+    pending_target_waiters: Vec<(TargetFilter, OneshotSender<Page>)>,
 }
 
 impl Handler {
-    pub(crate) fn new(mut conn: Connection<CdpEventMessage>, rx: Receiver<HandlerMessage>) -> Self {
+    pub(crate) fn new(
+        mut conn: Connection<CdpEventMessage>,
+        rx: Receiver<HandlerMessage>,
+        config: HandlerConfig,
+    ) -> Self {
         let discover = SetDiscoverTargetsParams::new(true);
         let _ = conn.submit_command(
             discover.identifier(),
@@ -86,12 +188,24 @@ impl Handler {
             targets: Default::default(),
             navigations: Default::default(),
             sessions: Default::default(),
+            subscriptions: Default::default(),
+            raw_sessions: Default::default(),
             conn,
             evict_command_timeout: Default::default(),
             next_navigation_id: 0,
+            next_proxy_id: 0,
+            config,
+            reconnect: None,
+            pending_target_waiters: Default::default(),
         }
     }
 
+    /// Computes the deadline for a command about to be submitted for
+    /// `method`, based on the handler's `HandlerConfig`.
+    fn command_deadline(&self, method: &str, now: Instant) -> Instant {
+        now + self.config.timeout_for(method)
+    }
+
     /// Return the target with the matching `target_id`
     pub fn get_target(&self, target_id: &TargetId) -> Option<&Target> {
         self.targets.get(target_id)
@@ -179,6 +293,134 @@ impl Handler {
                         target.on_response(resp);
                     }
                 }
+                PendingRequest::RawProxy(id) => {
+                    self.on_raw_proxy_response(id, resp);
+                }
+            }
+        }
+    }
+
+    /// Attempts to re-dial the browser's websocket. On success, re-runs the
+    /// discovery bootstrap and re-attaches the sessions of every target we
+    /// still track. On failure, re-arms the backoff for another attempt, or,
+    /// once `max_retries` is exhausted, returns a terminal error that ends
+    /// the handler's stream instead of silently retrying forever.
+    fn try_reconnect(&mut self, state: ReconnectState) -> Option<CdpError> {
+        match self.conn.reconnect() {
+            Ok(()) => {
+                self.resync_after_reconnect();
+                None
+            }
+            Err(_) => {
+                let attempt = state.attempt + 1;
+                let retries_exhausted = self
+                    .config
+                    .reconnect
+                    .max_retries
+                    .map(|max| attempt >= max)
+                    .unwrap_or(false);
+                if retries_exhausted {
+                    Some(CdpError::Disconnected)
+                } else {
+                    self.reconnect = Some(ReconnectState {
+                        attempt,
+                        next_attempt: Instant::now() + self.config.reconnect.backoff_for(attempt),
+                        timer_armed: false,
+                    });
+                    None
+                }
+            }
+        }
+    }
+
+    /// Re-establishes handler state that only lives on the websocket
+    /// connection: re-issues the `Target.setDiscoverTargets` bootstrap and
+    /// re-attaches every target we were still tracking before the drop.
+    fn resync_after_reconnect(&mut self) {
+        let discover = SetDiscoverTargetsParams::new(true);
+        let _ = self.conn.submit_command(
+            discover.identifier(),
+            None,
+            serde_json::to_value(discover).unwrap(),
+        );
+
+        for target_id in self.target_ids.clone() {
+            let attach = AttachToTargetParams::new(target_id);
+            if let Ok(params) = serde_json::to_value(&attach) {
+                let _ = self.conn.submit_command(attach.identifier(), None, params);
+            }
+        }
+    }
+
+    /// Fails every currently pending command with `CdpError::Disconnected`
+    /// rather than letting it hang forever after a reconnect.
+    fn fail_in_flight_commands_with_disconnect(&mut self) {
+        let call_ids: Vec<CallId> = self.pending_commands.keys().copied().collect();
+        for call_id in call_ids {
+            if let Some((req, _)) = self.pending_commands.remove(&call_id) {
+                self.fail_pending(call_id, req, CdpError::Disconnected);
+            }
+        }
+    }
+
+    /// Removes every pending command whose deadline has passed and resolves
+    /// its waiter with a timeout error, so a browser that never answers a
+    /// command can't leak the pending entry and hang the caller forever.
+    fn evict_timed_out_commands(&mut self, now: Instant) {
+        let timed_out: Vec<CallId> = self
+            .pending_commands
+            .iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for call_id in timed_out {
+            if let Some((req, _)) = self.pending_commands.remove(&call_id) {
+                self.fail_pending(call_id, req, CdpError::Timeout);
+            }
+        }
+    }
+
+    /// Resolves a removed `pending_commands` entry with `err`, used by both
+    /// `fail_in_flight_commands_with_disconnect` and
+    /// `evict_timed_out_commands` so the two share one per-variant dispatch
+    /// instead of drifting apart.
+    fn fail_pending(&mut self, call_id: CallId, req: PendingRequest, err: CdpError) {
+        match req {
+            PendingRequest::CreateTarget(tx) => {
+                let _ = tx.send(Err(err)).ok();
+            }
+            PendingRequest::ExternalCommand(tx) => {
+                let _ = tx.send(Err(err)).ok();
+            }
+            PendingRequest::Navigate(id) => {
+                self.on_navigation_lifecycle_completed(Err(NavigationError::new(id, err)));
+            }
+            PendingRequest::InternalCommand(target_id) => {
+                if let Some(target) = self.targets.get_mut(&target_id) {
+                    target.on_request_timeout(call_id);
+                }
+            }
+            PendingRequest::RawProxy(id) => {
+                self.fail_raw_proxy_command(id, call_id, err);
+            }
+        }
+    }
+
+    /// Sends a JSON-RPC-style error response to a raw proxy's frontend for a
+    /// command that never got a response from the browser (eviction timeout,
+    /// or the connection dropping and being re-dialed), so the external
+    /// caller's request id doesn't just vanish.
+    fn fail_raw_proxy_command(&mut self, id: ProxyId, call_id: CallId, err: CdpError) {
+        if let Some(proxy) = self.raw_sessions.get_mut(&id) {
+            if let Some(external_id) = proxy.pending.remove(&call_id) {
+                let out = serde_json::json!({
+                    "id": external_id,
+                    "error": { "message": err.to_string() },
+                });
+                if let Ok(json) = serde_json::to_string(&out) {
+                    let _ = proxy.tx.unbounded_send(json);
+                }
             }
         }
     }
@@ -188,11 +430,15 @@ impl Handler {
         msg: CommandMessage,
         now: Instant,
     ) -> Result<(), CdpError> {
+        let method = msg.method.clone();
         let call_id = self
             .conn
             .submit_command(msg.method, msg.session_id, msg.params)?;
-        self.pending_commands
-            .insert(call_id, (PendingRequest::ExternalCommand(msg.sender), now));
+        let deadline = self.command_deadline(method.as_ref(), now);
+        self.pending_commands.insert(
+            call_id,
+            (PendingRequest::ExternalCommand(msg.sender), deadline),
+        );
         Ok(())
     }
 
@@ -202,22 +448,26 @@ impl Handler {
         req: CdpRequest,
         now: Instant,
     ) -> Result<(), CdpError> {
+        let method = req.method.clone();
         let call_id =
             self.conn
                 .submit_command(req.method, req.session_id.map(Into::into), req.params)?;
+        let deadline = self.command_deadline(method.as_ref(), now);
         self.pending_commands
-            .insert(call_id, (PendingRequest::InternalCommand(target_id), now));
+            .insert(call_id, (PendingRequest::InternalCommand(target_id), deadline));
         Ok(())
     }
 
     fn submit_navigation(&mut self, id: NavigationId, req: CdpRequest, now: Instant) {
+        let method = req.method.clone();
         let call_id = self
             .conn
             .submit_command(req.method, req.session_id.map(Into::into), req.params)
             .unwrap();
 
+        let deadline = self.command_deadline(method.as_ref(), now);
         self.pending_commands
-            .insert(call_id, (PendingRequest::Navigate(id), now));
+            .insert(call_id, (PendingRequest::Navigate(id), deadline));
     }
 
     /// Process a message received by the target
@@ -243,6 +493,110 @@ impl Handler {
         id
     }
 
+    fn next_proxy_id(&mut self) -> ProxyId {
+        let id = ProxyId(self.next_proxy_id);
+        self.next_proxy_id = self.next_proxy_id.wrapping_add(1);
+        id
+    }
+
+    /// Attaches a raw CDP passthrough session so an external consumer can
+    /// exchange raw JSON CDP messages with `target_id` without going through
+    /// the typed command API.
+    fn attach_raw_session(
+        &mut self,
+        target_id: TargetId,
+        tx: mpsc::UnboundedSender<String>,
+        rx: mpsc::UnboundedReceiver<String>,
+    ) {
+        let id = self.next_proxy_id();
+        self.raw_sessions.insert(
+            id,
+            RawSessionProxy {
+                target_id,
+                tx,
+                rx: rx.fuse(),
+                pending: Default::default(),
+            },
+        );
+    }
+
+    /// Parses a single raw JSON CDP command from a proxy and submits it,
+    /// remapping the external frontend's own request id into the
+    /// connection's `CallId` space.
+    fn submit_raw_command(&mut self, id: ProxyId, msg: String, now: Instant) {
+        let cmd = match parse_raw_command(&msg) {
+            Some(cmd) => cmd,
+            None => return,
+        };
+
+        let deadline = self.command_deadline(&cmd.method, now);
+        if let Ok(call_id) =
+            self.conn
+                .submit_command(cmd.method.into(), cmd.session_id, cmd.params)
+        {
+            if let Some(proxy) = self.raw_sessions.get_mut(&id) {
+                proxy.pending.insert(call_id, cmd.external_id);
+            }
+            self.pending_commands
+                .insert(call_id, (PendingRequest::RawProxy(id), deadline));
+        }
+    }
+
+    /// Re-serializes a response addressed to a raw proxy's pending command
+    /// back into the external frontend's own request id and pushes it out.
+    fn on_raw_proxy_response(&mut self, id: ProxyId, resp: Response) {
+        if let Some(proxy) = self.raw_sessions.get_mut(&id) {
+            if let Some(external_id) = proxy.pending.remove(&resp.id) {
+                let mut out = serde_json::json!({ "id": external_id });
+                if let Some(result) = resp.result {
+                    out["result"] = result;
+                }
+                if let Some(error) = resp.error {
+                    out["error"] = serde_json::to_value(error).unwrap_or(serde_json::Value::Null);
+                }
+                if let Ok(json) = serde_json::to_string(&out) {
+                    let _ = proxy.tx.unbounded_send(json);
+                }
+            }
+        }
+    }
+
+    /// Streams a CDP event as a raw JSON notification to every raw proxy
+    /// attached to the event's target.
+    fn dispatch_raw_proxies(&self, event: &CdpEventMessage) {
+        if self.raw_sessions.is_empty() {
+            return;
+        }
+        let json = match serde_json::to_value(&event.params) {
+            Ok(params) => {
+                let mut value = serde_json::json!({
+                    "method": event.params.identifier().as_ref(),
+                    "params": params,
+                });
+                if let Some(session_id) = &event.session_id {
+                    value["sessionId"] =
+                        serde_json::to_value(session_id).unwrap_or(serde_json::Value::Null);
+                }
+                match serde_json::to_string(&value) {
+                    Ok(json) => json,
+                    Err(_) => return,
+                }
+            }
+            Err(_) => return,
+        };
+        for proxy in self.raw_sessions.values() {
+            let matches = self
+                .targets
+                .get(&proxy.target_id)
+                .and_then(|target| target.session_id())
+                .map(|session_id| Some(session_id) == event.session_id.as_ref())
+                .unwrap_or(false);
+            if matches {
+                let _ = proxy.tx.unbounded_send(json.clone());
+            }
+        }
+    }
+
     /// Create a new page and send it to the receiver
     fn create_page(
         &mut self,
@@ -250,11 +604,12 @@ impl Handler {
         tx: OneshotSender<Result<Page, CdpError>>,
     ) {
         let method = params.identifier();
+        let deadline = self.command_deadline(method.as_ref(), Instant::now());
         match serde_json::to_value(params) {
             Ok(params) => match self.conn.submit_command(method, None, params) {
                 Ok(call_id) => {
                     self.pending_commands
-                        .insert(call_id, (PendingRequest::CreateTarget(tx), Instant::now()));
+                        .insert(call_id, (PendingRequest::CreateTarget(tx), deadline));
                 }
                 Err(err) => {
                     let _ = tx.send(Err(err.into())).ok();
@@ -266,7 +621,39 @@ impl Handler {
         }
     }
 
+    /// Registers a new subscriber that wants to be notified of every event
+    /// matching `method`, optionally scoped to a single target's session.
+    fn add_event_listener(
+        &mut self,
+        method: &'static str,
+        session_filter: Option<SessionId>,
+        tx: mpsc::UnboundedSender<Arc<CdpEventMessage>>,
+    ) {
+        self.subscriptions
+            .entry(method)
+            .or_insert_with(Vec::new)
+            .push(EventListener { session_filter, tx });
+    }
+
+    /// Clone-forwards `event` to every subscriber registered for its method,
+    /// dropping any whose receiving end has gone away.
+    fn dispatch_event(&mut self, event: &CdpEventMessage) {
+        let method = event.params.identifier();
+        if let Some(listeners) = self.subscriptions.get_mut(method.as_ref()) {
+            listeners.retain(|listener| {
+                if !session_matches(&listener.session_filter, &event.session_id) {
+                    // not targeted at this listener; keep it only while it's alive
+                    return !listener.tx.is_closed();
+                }
+                listener.tx.unbounded_send(Arc::new(event.clone())).is_ok()
+            });
+        }
+    }
+
     fn on_event(&mut self, event: CdpEventMessage) {
+        self.dispatch_event(&event);
+        self.dispatch_raw_proxies(&event);
+
         if let Some(ref session_id) = event.session_id {
             if let Some(session) = self.sessions.get(session_id) {
                 if let Some(target) = self.targets.get_mut(session.target_id()) {
@@ -279,17 +666,85 @@ impl Handler {
             CdpEvent::TargetAttachedToTarget(ev) => self.on_attached_to_target(ev),
             CdpEvent::TargetTargetDestroyed(ev) => self.on_target_destroyed(ev),
             CdpEvent::TargetDetachedFromTarget(ev) => self.on_detached_from_target(ev),
+            CdpEvent::TargetTargetInfoChanged(ev) => self.on_target_info_changed(ev),
             _ => {}
         }
     }
 
+    /// Fired whenever a target's `TargetInfo` changes, most commonly after a
+    /// navigation updates its URL. Refreshes the stored info and re-checks
+    /// pending `WaitForTarget` waiters, since a `TargetFilter::url` predicate
+    /// can only match once the post-navigation URL has landed here.
+    fn on_target_info_changed(&mut self, event: EventTargetInfoChanged) {
+        let target_id = event.target_info.target_id.clone();
+        if let Some(target) = self.targets.get_mut(&target_id) {
+            target.set_info(event.target_info);
+        }
+        self.check_target_waiters(&target_id);
+    }
+
     /// Fired when a new target was created on the chromium instance
     ///
     /// Creates a new `Target` instance and keeps track of it
     fn on_target_created(&mut self, event: EventTargetCreated) {
         let target = Target::new(event.target_info);
-        self.target_ids.push(target.target_id().clone());
-        self.targets.insert(target.target_id().clone(), target);
+        let target_id = target.target_id().clone();
+        self.target_ids.push(target_id.clone());
+        self.targets.insert(target_id.clone(), target);
+        self.check_target_waiters(&target_id);
+    }
+
+    /// Resolves the first registered `WaitForTarget` waiter whose filter
+    /// matches `target_id`'s current info, provided the target's page has
+    /// already been initialized.
+    fn check_target_waiters(&mut self, target_id: &TargetId) {
+        if self.pending_target_waiters.is_empty() {
+            return;
+        }
+        let info = match self.targets.get(target_id) {
+            Some(target) => target.info().clone(),
+            None => return,
+        };
+        let position = self
+            .pending_target_waiters
+            .iter()
+            .position(|(filter, _)| filter.matches(&info));
+        let position = match position {
+            Some(position) => position,
+            None => return,
+        };
+        let page = match self
+            .targets
+            .get_mut(target_id)
+            .and_then(|target| target.get_or_create_page())
+        {
+            Some(page) => Page::from(page.clone()),
+            None => return,
+        };
+        let (_, tx) = self.pending_target_waiters.remove(position);
+        let _ = tx.send(page);
+    }
+
+    /// Registers a new `WaitForTarget` waiter, resolving it immediately if a
+    /// matching target with an initialized page already exists.
+    fn register_target_waiter(&mut self, filter: TargetFilter, tx: OneshotSender<Page>) {
+        let already_matching = self
+            .targets
+            .iter()
+            .find(|(_, target)| filter.matches(target.info()))
+            .map(|(id, _)| id.clone());
+
+        if let Some(target_id) = already_matching {
+            if let Some(page) = self
+                .targets
+                .get_mut(&target_id)
+                .and_then(|target| target.get_or_create_page())
+            {
+                let _ = tx.send(Page::from(page.clone()));
+                return;
+            }
+        }
+        self.pending_target_waiters.push((filter, tx));
     }
 
     fn on_attached_to_target(&mut self, event: EventAttachedToTarget) {
@@ -352,7 +807,51 @@ impl Stream for Handler {
                         .collect();
                     let _ = tx.send(pages);
                 }
-                HandlerMessage::Subscribe => {}
+                HandlerMessage::AddEventListener {
+                    method,
+                    session_filter,
+                    tx,
+                } => {
+                    pin.add_event_listener(method, session_filter, tx);
+                }
+                HandlerMessage::AttachRawSession { target_id, tx, rx } => {
+                    pin.attach_raw_session(target_id, tx, rx);
+                }
+                HandlerMessage::WaitForTarget { filter, tx } => {
+                    pin.register_target_waiter(filter, tx);
+                }
+            }
+        }
+
+        for id in pin.raw_sessions.keys().copied().collect::<Vec<_>>() {
+            let mut proxy = match pin.raw_sessions.remove(&id) {
+                Some(proxy) => proxy,
+                None => continue,
+            };
+            // Once `rx` is closed the external frontend is gone for good; drop
+            // the proxy instead of reinserting it, or it would sit around
+            // forever and every `dispatch_raw_proxies` call would keep
+            // iterating over it.
+            let mut closed = false;
+            loop {
+                match Pin::new(&mut proxy.rx).poll_next(cx) {
+                    Poll::Ready(Some(msg)) => {
+                        pin.raw_sessions.insert(id, proxy);
+                        pin.submit_raw_command(id, msg, now);
+                        proxy = match pin.raw_sessions.remove(&id) {
+                            Some(proxy) => proxy,
+                            None => break,
+                        };
+                    }
+                    Poll::Ready(None) => {
+                        closed = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+            if !closed {
+                pin.raw_sessions.insert(id, proxy);
             }
         }
 
@@ -382,22 +881,61 @@ impl Stream for Handler {
                 }
 
                 pin.targets.insert(id, target);
+                pin.check_target_waiters(&target_id);
                 pin.target_ids.push(target_id);
             }
         }
 
-        while let Poll::Ready(Some(ev)) = Pin::new(&mut pin.conn).poll_next(cx) {
-            match ev {
-                Ok(Message::Response(resp)) => pin.on_response(resp),
-                Ok(Message::Event(ev)) => {
-                    pin.on_event(ev);
+        if let Some(mut state) = pin.reconnect.take() {
+            if now >= state.next_attempt {
+                if let Some(err) = pin.try_reconnect(state) {
+                    return Poll::Ready(Some(Err(err)));
+                }
+            } else if state.timer_armed {
+                pin.reconnect = Some(state);
+            } else {
+                // Arm a one-shot timer that wakes this task once the backoff
+                // elapses, instead of re-polling immediately on every wakeup
+                // (which would busy-spin for the whole backoff window).
+                state.timer_armed = true;
+                let next_attempt = state.next_attempt;
+                let waker = cx.waker().clone();
+                std::thread::spawn(move || {
+                    let now = Instant::now();
+                    if next_attempt > now {
+                        std::thread::sleep(next_attempt - now);
+                    }
+                    waker.wake();
+                });
+                pin.reconnect = Some(state);
+            }
+        } else {
+            while let Poll::Ready(Some(ev)) = Pin::new(&mut pin.conn).poll_next(cx) {
+                match ev {
+                    Ok(Message::Response(resp)) => pin.on_response(resp),
+                    Ok(Message::Event(ev)) => {
+                        pin.on_event(ev);
+                    }
+                    Err(err) => {
+                        if pin.config.reconnect.enabled {
+                            pin.fail_in_flight_commands_with_disconnect();
+                            pin.reconnect = Some(ReconnectState {
+                                attempt: 0,
+                                next_attempt: now + pin.config.reconnect.backoff_for(0),
+                                timer_armed: false,
+                            });
+                            cx.waker().wake_by_ref();
+                            break;
+                        } else {
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    }
                 }
-                Err(err) => return Poll::Ready(Some(Err(err))),
             }
         }
 
         if pin.evict_command_timeout.is_ready(cx) {
-            // TODO evict all commands that timed out
+            pin.evict_timed_out_commands(now);
         }
 
         Poll::Pending
@@ -451,6 +989,76 @@ enum PendingRequest {
     Navigate(NavigationId),
     ExternalCommand(OneshotSender<Result<Response, CdpError>>),
     InternalCommand(TargetId),
+    RawProxy(ProxyId),
+}
+
+/// Identifies a single attached [`RawSessionProxy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ProxyId(usize);
+
+/// A raw CDP passthrough session attached via
+/// `HandlerMessage::AttachRawSession`, bridging an external DevTools
+/// frontend to a single target without going through the typed command API.
+struct RawSessionProxy {
+    target_id: TargetId,
+    /// Raw JSON CDP messages (command responses + forwarded notifications)
+    /// towards the external frontend.
+    tx: mpsc::UnboundedSender<String>,
+    /// Raw JSON CDP commands coming in from the external frontend.
+    rx: Fuse<mpsc::UnboundedReceiver<String>>,
+    /// Maps a command's internal `CallId` back to the external frontend's own
+    /// request id so the response can be re-addressed on the way out.
+    pending: FnvHashMap<CallId, serde_json::Value>,
+}
+
+/// A raw CDP command parsed out of a proxy's incoming JSON message.
+struct RawCommand {
+    external_id: serde_json::Value,
+    method: String,
+    params: serde_json::Value,
+    session_id: Option<SessionId>,
+}
+
+/// Parses a single raw JSON CDP command sent by an external DevTools
+/// frontend through a [`RawSessionProxy`]. Returns `None` for malformed
+/// input: not valid JSON, or missing the `id`/`method` fields every CDP
+/// command needs.
+fn parse_raw_command(msg: &str) -> Option<RawCommand> {
+    let mut value: serde_json::Value = serde_json::from_str(msg).ok()?;
+    let external_id = value.get("id").cloned()?;
+    let method = value.get("method").and_then(|m| m.as_str())?.to_string();
+    let params = value
+        .as_object_mut()
+        .and_then(|obj| obj.remove("params"))
+        .unwrap_or(serde_json::Value::Null);
+    let session_id = value
+        .get("sessionId")
+        .and_then(|s| s.as_str())
+        .map(|s| SessionId::from(s.to_string()));
+    Some(RawCommand {
+        external_id,
+        method,
+        params,
+        session_id,
+    })
+}
+
+/// A single subscriber registered via [`HandlerMessage::AddEventListener`].
+#[derive(Debug)]
+struct EventListener {
+    /// If set, only events carrying this session id are forwarded to `tx`.
+    session_filter: Option<SessionId>,
+    tx: mpsc::UnboundedSender<Arc<CdpEventMessage>>,
+}
+
+/// Whether an event carrying `event_session` should be forwarded to a
+/// listener registered with `filter` as its `session_filter`: unscoped
+/// listeners (`None`) take everything, scoped ones only an exact match.
+fn session_matches(filter: &Option<SessionId>, event_session: &Option<SessionId>) -> bool {
+    match filter {
+        Some(filter) => event_session.as_ref() == Some(filter),
+        None => true,
+    }
 }
 
 /// Events used internally to communicate with the handler, which are executed
@@ -461,7 +1069,112 @@ pub(crate) enum HandlerMessage {
     CreatePage(CreateTargetParams, OneshotSender<Result<Page, CdpError>>),
     GetPages(OneshotSender<Vec<Page>>),
     Command(CommandMessage),
-    Subscribe,
+    /// Subscribe to every event matching `method`, optionally scoped to a
+    /// single target's session.
+    AddEventListener {
+        method: &'static str,
+        session_filter: Option<SessionId>,
+        tx: mpsc::UnboundedSender<Arc<CdpEventMessage>>,
+    },
+    /// Attach a raw JSON CDP passthrough session to `target_id`.
+    AttachRawSession {
+        target_id: TargetId,
+        tx: mpsc::UnboundedSender<String>,
+        rx: mpsc::UnboundedReceiver<String>,
+    },
+    /// Resolve `tx` with the first target matching `filter`, be it one that
+    /// already exists or the next one to be created (e.g. a popup opened via
+    /// `window.open`).
+    WaitForTarget {
+        filter: TargetFilter,
+        tx: OneshotSender<Page>,
+    },
+}
+
+/// Criteria used to match a target for `HandlerMessage::WaitForTarget`, see
+/// `Page::wait_for_popup`/`Browser::wait_for_target`.
+pub struct TargetFilter {
+    /// Only match targets of this CDP target type (e.g. `"page"`).
+    kind: Option<String>,
+    /// Only match targets opened by this target (e.g. via `window.open` or a
+    /// `target="_blank"` link).
+    opener_id: Option<TargetId>,
+    /// Only match targets whose URL satisfies this predicate.
+    url: Option<Box<dyn Fn(&str) -> bool + Send>>,
+}
+
+// `url` is a `Box<dyn Fn>`, which isn't `Debug`, so this can't be derived;
+// implemented by hand so `HandlerMessage` (which embeds a `TargetFilter` in
+// `WaitForTarget`) can keep its own `#[derive(Debug)]`.
+impl std::fmt::Debug for TargetFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TargetFilter")
+            .field("kind", &self.kind)
+            .field("opener_id", &self.opener_id)
+            .field("url", &self.url.as_ref().map(|_| "Fn(&str) -> bool"))
+            .finish()
+    }
+}
+
+impl TargetFilter {
+    /// Matches any target; narrow it down with `kind`/`opener_id`/`url`.
+    pub fn new() -> Self {
+        Self {
+            kind: None,
+            opener_id: None,
+            url: None,
+        }
+    }
+
+    /// Convenience filter for regular pages (as opposed to e.g. workers).
+    pub fn page() -> Self {
+        Self::new().kind("page")
+    }
+
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    pub fn opener_id(mut self, opener_id: TargetId) -> Self {
+        self.opener_id = Some(opener_id);
+        self
+    }
+
+    pub fn url(mut self, predicate: impl Fn(&str) -> bool + Send + 'static) -> Self {
+        self.url = Some(Box::new(predicate));
+        self
+    }
+
+    fn matches(&self, info: &TargetInfo) -> bool {
+        self.kind_matches(&info.r#type)
+            && self.opener_matches(info.opener_id.as_ref())
+            && self.url_matches(&info.url)
+    }
+
+    /// Whether `kind` satisfies this filter's `kind` criterion, if any.
+    fn kind_matches(&self, kind: &str) -> bool {
+        self.kind.as_deref().map_or(true, |expected| expected == kind)
+    }
+
+    /// Whether `opener_id` satisfies this filter's `opener_id` criterion, if
+    /// any.
+    fn opener_matches(&self, opener_id: Option<&TargetId>) -> bool {
+        self.opener_id
+            .as_ref()
+            .map_or(true, |expected| Some(expected) == opener_id)
+    }
+
+    /// Whether `url` satisfies this filter's `url` predicate, if any.
+    fn url_matches(&self, url: &str) -> bool {
+        self.url.as_ref().map_or(true, |predicate| predicate(url))
+    }
+}
+
+impl Default for TargetFilter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 pub(crate) fn to_command_response<T: Command>(
@@ -480,3 +1193,96 @@ pub(crate) fn to_command_response<T: Command>(
         Err(CdpError::NoResponse)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_raw_command_extracts_method_params_and_session() {
+        let msg = r#"{"id": 7, "method": "Page.navigate", "params": {"url": "http://example.com"}, "sessionId": "abc"}"#;
+        let cmd = parse_raw_command(msg).expect("valid raw command");
+        assert_eq!(cmd.external_id, serde_json::json!(7));
+        assert_eq!(cmd.method, "Page.navigate");
+        assert_eq!(cmd.params, serde_json::json!({"url": "http://example.com"}));
+        assert!(cmd.session_id.is_some());
+    }
+
+    #[test]
+    fn parse_raw_command_defaults_missing_params_to_null() {
+        let msg = r#"{"id": 1, "method": "Page.enable"}"#;
+        let cmd = parse_raw_command(msg).expect("valid raw command");
+        assert_eq!(cmd.params, serde_json::Value::Null);
+        assert!(cmd.session_id.is_none());
+    }
+
+    #[test]
+    fn parse_raw_command_rejects_malformed_input() {
+        assert!(parse_raw_command("not json").is_none());
+        assert!(parse_raw_command(r#"{"method": "Page.enable"}"#).is_none());
+        assert!(parse_raw_command(r#"{"id": 1}"#).is_none());
+    }
+
+    #[test]
+    fn handler_config_falls_back_to_default_timeout() {
+        let config = HandlerConfig::default();
+        assert_eq!(config.timeout_for("Page.navigate"), config.request_timeout);
+    }
+
+    #[test]
+    fn handler_config_uses_per_method_override() {
+        let mut config = HandlerConfig::default();
+        config
+            .request_timeouts
+            .insert("Page.navigate", Duration::from_secs(60));
+        assert_eq!(config.timeout_for("Page.navigate"), Duration::from_secs(60));
+        assert_eq!(config.timeout_for("Page.enable"), config.request_timeout);
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_then_caps() {
+        let policy = ReconnectPolicy {
+            enabled: true,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(4),
+            max_retries: None,
+        };
+        assert_eq!(policy.backoff_for(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff_for(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff_for(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff_for(3), Duration::from_secs(4));
+        // capped, not allowed to keep growing past max_backoff
+        assert_eq!(policy.backoff_for(10), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn session_matches_unscoped_listener_takes_everything() {
+        assert!(session_matches(&None, &None));
+        assert!(session_matches(&None, &Some(SessionId::from("a".to_string()))));
+    }
+
+    #[test]
+    fn session_matches_scoped_listener_requires_exact_match() {
+        let a = SessionId::from("a".to_string());
+        let b = SessionId::from("b".to_string());
+        assert!(session_matches(&Some(a.clone()), &Some(a.clone())));
+        assert!(!session_matches(&Some(a.clone()), &Some(b)));
+        assert!(!session_matches(&Some(a), &None));
+    }
+
+    #[test]
+    fn target_filter_kind_matches() {
+        let filter = TargetFilter::new().kind("page");
+        assert!(filter.kind_matches("page"));
+        assert!(!filter.kind_matches("worker"));
+        assert!(TargetFilter::new().kind_matches("anything"));
+    }
+
+    #[test]
+    fn target_filter_url_matches() {
+        let filter = TargetFilter::new().url(|url| url.starts_with("https://accounts.example.com"));
+        assert!(filter.url_matches("https://accounts.example.com/oauth"));
+        assert!(!filter.url_matches("https://example.com"));
+        assert!(TargetFilter::new().url_matches("anything"));
+    }
+}
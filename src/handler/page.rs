@@ -0,0 +1,26 @@
+use futures::channel::mpsc::Sender;
+
+use crate::handler::target::TargetId;
+use crate::handler::HandlerMessage;
+
+/// Shared state behind the public [`crate::page::Page`] handle; cheaply
+/// wrapped in an `Arc` and used to talk back to the `Handler` running in the
+/// background.
+pub(crate) struct PageInner {
+    target_id: TargetId,
+    sender: Sender<HandlerMessage>,
+}
+
+impl PageInner {
+    pub(crate) fn new(target_id: TargetId, sender: Sender<HandlerMessage>) -> Self {
+        Self { target_id, sender }
+    }
+
+    pub(crate) fn target_id(&self) -> &TargetId {
+        &self.target_id
+    }
+
+    pub(crate) fn sender(&self) -> Sender<HandlerMessage> {
+        self.sender.clone()
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::channel::oneshot;
+use futures::stream::Stream;
+
+use crate::cdp::events::CdpEventMessage;
+use crate::error::CdpError;
+use crate::handler::page::PageInner;
+use crate::handler::{HandlerMessage, TargetFilter};
+
+/// A handle to a single page (tab) running inside the browser.
+#[derive(Clone)]
+pub struct Page {
+    inner: Arc<PageInner>,
+}
+
+impl From<Arc<PageInner>> for Page {
+    fn from(inner: Arc<PageInner>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Page {
+    /// Subscribes to every event matching the CDP method identifier
+    /// `method`.
+    ///
+    /// The listener is registered without a session filter: the stream is
+    /// not scoped to this page's session and carries matching events of
+    /// every target.
+    pub async fn event_listener(
+        &self,
+        method: &'static str,
+    ) -> Result<impl Stream<Item = Arc<CdpEventMessage>>, CdpError> {
+        let (tx, rx) = mpsc::unbounded();
+        let mut sender = self.inner.sender();
+        sender
+            .try_send(HandlerMessage::AddEventListener {
+                method,
+                session_filter: None,
+                tx,
+            })
+            .map_err(|_| CdpError::Disconnected)?;
+        Ok(rx)
+    }
+
+    /// Resolves with a page opened by this page, e.g. via `window.open` or a
+    /// `target="_blank"` link: one that already exists, or else the next one
+    /// to be opened.
+    pub async fn wait_for_popup(&self) -> Result<Page, CdpError> {
+        let (tx, rx) = oneshot::channel();
+        let filter = TargetFilter::page().opener_id(self.inner.target_id().clone());
+        let mut sender = self.inner.sender();
+        sender
+            .try_send(HandlerMessage::WaitForTarget { filter, tx })
+            .map_err(|_| CdpError::Disconnected)?;
+        rx.await.map_err(|_| CdpError::Disconnected)
+    }
+
+    /// Attaches a raw CDP passthrough session to this page, letting an
+    /// external consumer (e.g. a custom DevTools UI) exchange raw JSON CDP
+    /// messages with it without going through the typed command API.
+    ///
+    /// Returns a duplex `(outgoing, incoming)` pair: send raw JSON commands
+    /// on `outgoing`, receive raw JSON responses/events on `incoming`.
+    pub fn raw_session(
+        &self,
+    ) -> Result<(UnboundedSender<String>, UnboundedReceiver<String>), CdpError> {
+        let (to_frontend_tx, to_frontend_rx) = mpsc::unbounded();
+        let (from_frontend_tx, from_frontend_rx) = mpsc::unbounded();
+        let mut sender = self.inner.sender();
+        sender
+            .try_send(HandlerMessage::AttachRawSession {
+                target_id: self.inner.target_id().clone(),
+                tx: to_frontend_tx,
+                rx: from_frontend_rx,
+            })
+            .map_err(|_| CdpError::Disconnected)?;
+        Ok((from_frontend_tx, to_frontend_rx))
+    }
+}